@@ -0,0 +1,34 @@
+//! Runtime antenna control: receiver gain and TX driver power
+
+use crate::error::StatusCode;
+use crate::register::{Register, RxGain};
+use crate::Mfrc522;
+
+impl Mfrc522 {
+    /// Sets the receiver's signal voltage gain, without disturbing the
+    /// other `RFCfgReg` bits.
+    pub fn set_antenna_gain(&mut self, gain: RxGain) -> Result<(), StatusCode> {
+        let current = self.read_reg(Register::RFCfgReg)?;
+        self.write_reg(Register::RFCfgReg, (current & !0x70) | u8::from(gain))
+    }
+
+    /// Reads back the receiver's current gain setting.
+    pub fn antenna_gain(&mut self) -> Result<RxGain, StatusCode> {
+        Ok(RxGain::from_bits(self.read_reg(Register::RFCfgReg)?))
+    }
+
+    /// Switches on the TX1/TX2 antenna drivers, energizing the antenna's RF
+    /// field.
+    pub fn antenna_on(&mut self) -> Result<(), StatusCode> {
+        let current = self.read_reg(Register::TxControlReg)?;
+        if current & 0x03 != 0x03 {
+            self.write_reg(Register::TxControlReg, current | 0x03)?;
+        }
+        Ok(())
+    }
+
+    /// Switches off the TX1/TX2 antenna drivers.
+    pub fn antenna_off(&mut self) -> Result<(), StatusCode> {
+        self.clear_bit_mask(Register::TxControlReg, 0x03)
+    }
+}
@@ -0,0 +1,65 @@
+//! Error types returned by the driver
+
+use core::fmt;
+
+/// Outcome of a PICC transceive, mirroring the status codes reference
+/// MFRC522 drivers use to classify a failed command.
+#[derive(Debug)]
+pub enum StatusCode {
+    /// The underlying SPI transaction itself failed.
+    Internal(rppal::spi::Error),
+    /// Configuring or reading the IRQ GPIO pin failed.
+    Gpio(rppal::gpio::Error),
+    /// Two or more PICCs answered at once; the bit position of the first
+    /// collision (0-31, counted from the start of the UID) is reported so
+    /// the anti-collision loop can resolve it.
+    Collision(u8),
+    /// The chip did not finish the requested command before the driver
+    /// stopped waiting for it.
+    Timeout,
+    /// The FIFO buffer overflowed (`ErrorReg.BufferOvfl`) because more data
+    /// was received than there was room for.
+    NoRoom,
+    /// The CRC_A of a received frame didn't match (`ErrorReg.CRCErr`).
+    CrcWrong,
+    /// A received frame had a parity error (`ErrorReg.ParityErr`) or
+    /// violated the expected ISO/IEC 14443 framing (`ErrorReg.ProtocolErr`).
+    Invalid,
+    /// A response did not contain as many bytes as expected, a MIFARE value
+    /// block's redundant copies disagreed, or MIFARE authentication finished
+    /// without switching CRYPTO1 on.
+    Incomplete,
+    /// The PICC answered a MIFARE command with a NAK, or something other
+    /// than the expected 4-bit ACK (0x0A).
+    MifareNak,
+}
+
+impl fmt::Display for StatusCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatusCode::Internal(e) => write!(f, "SPI error: {}", e),
+            StatusCode::Gpio(e) => write!(f, "GPIO error: {}", e),
+            StatusCode::Collision(bit) => write!(f, "bit collision detected at bit {}", bit),
+            StatusCode::Timeout => write!(f, "timed out waiting for the MFRC522"),
+            StatusCode::NoRoom => write!(f, "FIFO buffer overflowed"),
+            StatusCode::CrcWrong => write!(f, "CRC_A check failed"),
+            StatusCode::Invalid => write!(f, "invalid or malformed response from the PICC"),
+            StatusCode::Incomplete => write!(f, "incomplete response from the MFRC522"),
+            StatusCode::MifareNak => write!(f, "PICC responded with NAK"),
+        }
+    }
+}
+
+impl std::error::Error for StatusCode {}
+
+impl From<rppal::spi::Error> for StatusCode {
+    fn from(e: rppal::spi::Error) -> Self {
+        StatusCode::Internal(e)
+    }
+}
+
+impl From<rppal::gpio::Error> for StatusCode {
+    fn from(e: rppal::gpio::Error) -> Self {
+        StatusCode::Gpio(e)
+    }
+}
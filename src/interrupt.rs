@@ -0,0 +1,192 @@
+//! Interrupt-driven card detection
+//!
+//! Rather than busy-polling the SPI bus with repeated REQAs, this routes
+//! `ComIrqReg`'s RxIRq/IdleIRq sources out to the MFRC522's hardware IRQ
+//! pin and runs a background thread that blocks on it with
+//! [`InputPin::poll_interrupt`], so callers can block on or be called back
+//! for tag-present/tag-removed events instead of spinning the SPI bus.
+
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use rppal::gpio::{InputPin, Trigger};
+
+use crate::error::StatusCode;
+use crate::picc::{bit_framing, PICC_REQA};
+use crate::register::Register;
+use crate::Mfrc522;
+
+/// A card-present/card-removed event delivered by a [`CardWatcher`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardEvent {
+    /// A PICC answered a REQA that wasn't already being tracked.
+    Present,
+    /// A previously-seen PICC stopped answering REQA.
+    Removed,
+}
+
+/// Builds a [`CardWatcher`], configuring which callbacks it invokes before
+/// handing the MFRC522 and IRQ pin over to its background thread.
+pub struct CardWatcherBuilder {
+    mfrc522: Mfrc522,
+    irq_pin: InputPin,
+    on_card: Option<Box<dyn FnMut() + Send>>,
+    on_card_removed: Option<Box<dyn FnMut() + Send>>,
+    rearm_interval: Duration,
+}
+
+impl Mfrc522 {
+    /// Starts building an interrupt-driven [`CardWatcher`], consuming
+    /// `self` and the IRQ pin wired to the MFRC522's IRQ output.
+    pub fn interrupt_driven(self, irq_pin: InputPin) -> CardWatcherBuilder {
+        CardWatcherBuilder {
+            mfrc522: self,
+            irq_pin,
+            on_card: None,
+            on_card_removed: None,
+            rearm_interval: Duration::from_millis(100),
+        }
+    }
+}
+
+impl CardWatcherBuilder {
+    /// Registers a callback run on the watcher thread the moment a PICC is
+    /// first seen.
+    pub fn on_card(mut self, f: impl FnMut() + Send + 'static) -> Self {
+        self.on_card = Some(Box::new(f));
+        self
+    }
+
+    /// Registers a callback run on the watcher thread when a previously
+    /// seen PICC stops answering REQA.
+    pub fn on_card_removed(mut self, f: impl FnMut() + Send + 'static) -> Self {
+        self.on_card_removed = Some(Box::new(f));
+        self
+    }
+
+    /// How often to rearm REQA while waiting for the IRQ pin, so the
+    /// absence of a PICC (which has no interrupt source of its own) is
+    /// still noticed within roughly this long.
+    /// Defaults to 100ms.
+    pub fn rearm_interval(mut self, interval: Duration) -> Self {
+        self.rearm_interval = interval;
+        self
+    }
+
+    /// Configures the chip to route RxIRq/IdleIRq to the hardware IRQ pin
+    /// and spawns the watcher thread, which blocks on that pin rather than
+    /// polling the SPI bus for each REQA.
+    pub fn start(mut self) -> Result<CardWatcher, StatusCode> {
+        // ComlEnReg: IRqInv (bit 7) so the IRQ pin idles high, RxIEn (bit 5)
+        // and IdleIEn (bit 4) routed out to it.
+        self.mfrc522.write_reg(Register::ComlEnReg, 0xB0)?;
+        // DivlEnReg: IRqPushPull (bit 7) drives the pin instead of open-drain.
+        self.mfrc522.write_reg(Register::DivlEnReg, 0x80)?;
+        self.irq_pin
+            .set_interrupt(Trigger::FallingEdge, None)
+            .map_err(StatusCode::Gpio)?;
+
+        let CardWatcherBuilder {
+            mut mfrc522,
+            mut irq_pin,
+            mut on_card,
+            mut on_card_removed,
+            rearm_interval,
+        } = self;
+
+        let (tx, rx) = channel();
+        let thread = thread::spawn(move || {
+            let mut present = false;
+            loop {
+                // REQA is a 7-bit frame: only the 7 least significant bits
+                // of the command byte are valid.
+                if mfrc522
+                    .write_reg(Register::BitFramingReg, bit_framing(0, 7))
+                    .and_then(|_| mfrc522.begin_transceive(&[PICC_REQA]))
+                    .is_err()
+                {
+                    break;
+                }
+
+                // Block on the IRQ pin itself instead of polling ComIrqReg
+                // over SPI: this is the whole point of routing RxIRq/IdleIRq
+                // out to hardware. rearm_interval still bounds how long a
+                // single REQA is given to answer, since a PICC's removal
+                // doesn't raise an interrupt of its own.
+                let completed = match irq_pin.poll_interrupt(true, Some(rearm_interval)) {
+                    Ok(Some(_)) => true,
+                    Ok(None) => false,
+                    Err(e) => {
+                        let _ = tx.send(Err(StatusCode::Gpio(e)));
+                        break;
+                    }
+                };
+
+                let answered = mfrc522.finish_transceive(completed).is_ok();
+                if answered && !present {
+                    present = true;
+                    if let Some(cb) = on_card.as_mut() {
+                        cb();
+                    }
+                    if tx.send(Ok(CardEvent::Present)).is_err() {
+                        break;
+                    }
+                } else if !answered && present {
+                    present = false;
+                    if let Some(cb) = on_card_removed.as_mut() {
+                        cb();
+                    }
+                    if tx.send(Ok(CardEvent::Removed)).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(CardWatcher {
+            events: rx,
+            _thread: thread,
+        })
+    }
+}
+
+/// A running interrupt-driven card watcher, started with
+/// [`Mfrc522::interrupt_driven`]. The IRQ pin is owned by its background
+/// thread for as long as the watcher lives.
+pub struct CardWatcher {
+    events: Receiver<Result<CardEvent, StatusCode>>,
+    _thread: JoinHandle<()>,
+}
+
+impl CardWatcher {
+    /// Blocks until a PICC is detected, ignoring any `Removed` events seen
+    /// in the meantime.
+    pub fn wait_for_card(&self) -> Result<(), StatusCode> {
+        for event in self.events.iter() {
+            match event? {
+                CardEvent::Present => return Ok(()),
+                CardEvent::Removed => continue,
+            }
+        }
+        Err(StatusCode::Timeout)
+    }
+
+    /// Blocks until a PICC is detected or `timeout` elapses.
+    pub fn wait_for_card_timeout(&self, timeout: Duration) -> Result<(), StatusCode> {
+        let deadline = std::time::Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+            if remaining.is_zero() {
+                return Err(StatusCode::Timeout);
+            }
+            match self.events.recv_timeout(remaining) {
+                Ok(Ok(CardEvent::Present)) => return Ok(()),
+                Ok(Ok(CardEvent::Removed)) => continue,
+                Ok(Err(e)) => return Err(e),
+                Err(RecvTimeoutError::Timeout) => return Err(StatusCode::Timeout),
+                Err(RecvTimeoutError::Disconnected) => return Err(StatusCode::Timeout),
+            }
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! A Raspberry Pi driver for the NXP MFRC522 RFID/NFC reader chip, built
+//! on top of [`rppal`]'s SPI support.
+
+pub mod antenna;
+pub mod error;
+pub mod interrupt;
+pub mod mifare;
+pub mod picc;
+pub mod power;
+pub mod register;
+pub mod self_test;
+pub mod uid;
+
+use rppal::spi::Spi;
+
+pub use error::StatusCode;
+use register::{Command, Register};
+
+/// Maximum number of polling iterations to wait for a command to finish
+/// before giving up with [`StatusCode::Timeout`].
+const COMMAND_TIMEOUT_ITERS: u32 = 2000;
+
+/// Driver for the MFRC522 RFID/NFC reader chip, talking to it over SPI.
+pub struct Mfrc522 {
+    spi: Spi,
+}
+
+impl Mfrc522 {
+    /// Wraps an already-configured [`Spi`] bus. The MFRC522 datasheet
+    /// specifies SPI mode 0 at up to 10 MHz.
+    pub fn new(spi: Spi) -> Self {
+        Mfrc522 { spi }
+    }
+
+    /// Reads a single register.
+    pub(crate) fn read_reg(&mut self, reg: Register) -> Result<u8, StatusCode> {
+        let address = 0x80 | ((u8::from(reg) << 1) & 0x7E);
+        let tx = [address, 0x00];
+        let mut rx = [0u8; 2];
+        self.spi.transfer(&mut rx, &tx)?;
+        Ok(rx[1])
+    }
+
+    /// Writes a single register.
+    pub(crate) fn write_reg(&mut self, reg: Register, value: u8) -> Result<(), StatusCode> {
+        let address = (u8::from(reg) << 1) & 0x7E;
+        self.spi.write(&[address, value])?;
+        Ok(())
+    }
+
+    /// Sets the bits of `mask` in `reg`, leaving the others untouched.
+    pub(crate) fn set_bit_mask(&mut self, reg: Register, mask: u8) -> Result<(), StatusCode> {
+        let current = self.read_reg(reg)?;
+        self.write_reg(reg, current | mask)
+    }
+
+    /// Clears the bits of `mask` in `reg`, leaving the others untouched.
+    pub(crate) fn clear_bit_mask(&mut self, reg: Register, mask: u8) -> Result<(), StatusCode> {
+        let current = self.read_reg(reg)?;
+        self.write_reg(reg, current & !mask)
+    }
+
+    /// Starts execution of `cmd`, cancelling whatever command is currently running.
+    pub(crate) fn command(&mut self, cmd: Command) -> Result<(), StatusCode> {
+        self.write_reg(Register::CommandReg, cmd.into())
+    }
+
+    /// Discards any bytes left over in the FIFO buffer from a previous command.
+    pub(crate) fn flush_fifo(&mut self) -> Result<(), StatusCode> {
+        self.set_bit_mask(Register::FIFOLevelReg, 0x80)
+    }
+
+    /// Loads `data` into the FIFO and starts [`Command::Transceive`],
+    /// without waiting for it to finish. Callers must follow up with
+    /// [`finish_transceive`](Self::finish_transceive) once the command has
+    /// had a chance to complete, whether that's established by polling
+    /// `ComIrqReg` over SPI (as [`transceive`](Self::transceive) does) or by
+    /// waiting on the MFRC522's hardware IRQ pin.
+    pub(crate) fn begin_transceive(&mut self, data: &[u8]) -> Result<(), StatusCode> {
+        self.command(Command::Idle)?;
+        self.write_reg(Register::ComIrqReg, 0x7F)?;
+        self.flush_fifo()?;
+        for &byte in data {
+            self.write_reg(Register::FIFODataReg, byte)?;
+        }
+        self.command(Command::Transceive)?;
+        self.set_bit_mask(Register::BitFramingReg, 0x80) // StartSend
+    }
+
+    /// Reads back the outcome of a command started with
+    /// [`begin_transceive`](Self::begin_transceive). `completed` tells it
+    /// whether RxIRq or IdleIRq was actually observed to fire; if not, this
+    /// reports [`StatusCode::Timeout`] without touching `ErrorReg` or the
+    /// FIFO.
+    pub(crate) fn finish_transceive(&mut self, completed: bool) -> Result<(Vec<u8>, u8), StatusCode> {
+        self.clear_bit_mask(Register::BitFramingReg, 0x80)?;
+        if !completed {
+            return Err(StatusCode::Timeout);
+        }
+
+        // ErrorReg bits, most specific first: CollErr also sets ProtocolErr,
+        // so it must be checked before the generic Invalid case below.
+        let error_reg = self.read_reg(Register::ErrorReg)?;
+        if error_reg & 0x10 != 0 {
+            // BufferOvfl
+            return Err(StatusCode::NoRoom);
+        }
+        if error_reg & 0x08 != 0 {
+            // CollErr: CollReg bit 5 (CollPosNotValid) means the collision
+            // happened past the last full byte we could make sense of.
+            let coll_reg = self.read_reg(Register::CollReg)?;
+            let coll_pos = if coll_reg & 0x20 != 0 {
+                0
+            } else {
+                let pos = coll_reg & 0x1F;
+                if pos == 0 { 31 } else { pos - 1 }
+            };
+            return Err(StatusCode::Collision(coll_pos));
+        }
+        if error_reg & 0x04 != 0 {
+            // CRCErr
+            return Err(StatusCode::CrcWrong);
+        }
+        if error_reg & 0x03 != 0 {
+            // ParityErr | ProtocolErr
+            return Err(StatusCode::Invalid);
+        }
+
+        let fifo_level = self.read_reg(Register::FIFOLevelReg)?;
+        let mut buf = vec![0u8; fifo_level as usize];
+        for byte in buf.iter_mut() {
+            *byte = self.read_reg(Register::FIFODataReg)?;
+        }
+        let valid_bits = self.read_reg(Register::ControlReg)? & 0x07;
+
+        Ok((buf, valid_bits))
+    }
+
+    /// Sends `data` to the PICC with [`Command::Transceive`] and returns
+    /// whatever it answers, along with the number of valid bits in the last
+    /// received byte (for bit-oriented frames such as REQA).
+    ///
+    /// This is the core helper all PICC and MIFARE level operations are
+    /// built on. It busy-polls `ComIrqReg` over SPI to find out when the
+    /// command finishes; [`CardWatcher`](crate::interrupt::CardWatcher)
+    /// uses [`begin_transceive`](Self::begin_transceive) and
+    /// [`finish_transceive`](Self::finish_transceive) directly instead, so
+    /// it can wait on the hardware IRQ pin rather than the SPI bus.
+    pub(crate) fn transceive(&mut self, data: &[u8]) -> Result<(Vec<u8>, u8), StatusCode> {
+        self.begin_transceive(data)?;
+
+        let mut completed = false;
+        for _ in 0..COMMAND_TIMEOUT_ITERS {
+            // RxIRq (bit 5) or IdleIRq (bit 4): the command has finished.
+            if self.read_reg(Register::ComIrqReg)? & 0x30 != 0 {
+                completed = true;
+                break;
+            }
+        }
+
+        self.finish_transceive(completed)
+    }
+
+    /// Like [`transceive`](Self::transceive), but enables the chip's CRC_A
+    /// coprocessor on transmit and receive, as required by SELECT and the
+    /// MIFARE commands.
+    pub(crate) fn transceive_with_crc(&mut self, data: &[u8]) -> Result<Vec<u8>, StatusCode> {
+        self.set_bit_mask(Register::TxModeReg, 0x80)?;
+        self.set_bit_mask(Register::RxModeReg, 0x80)?;
+        let result = self.transceive(data);
+        self.clear_bit_mask(Register::TxModeReg, 0x80)?;
+        self.clear_bit_mask(Register::RxModeReg, 0x80)?;
+        Ok(result?.0)
+    }
+}
@@ -0,0 +1,244 @@
+//! MIFARE Classic sector authentication, block read/write and value-block
+//! operations
+//!
+//! The actual CRYPTO1 cipher is implemented entirely on-chip; for
+//! authentication this module only drives the handshake described for
+//! [`Command::MFAuthent`](crate::register::Command::MFAuthent).
+
+use crate::error::StatusCode;
+use crate::register::{Command, Register};
+use crate::Mfrc522;
+
+/// Which of a sector's two keys to authenticate with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    /// Key A, command code 0x60.
+    A,
+    /// Key B, command code 0x61.
+    B,
+}
+
+impl Key {
+    fn command_byte(self) -> u8 {
+        match self {
+            Key::A => 0x60,
+            Key::B => 0x61,
+        }
+    }
+}
+
+impl Mfrc522 {
+    /// Authenticates against a MIFARE Classic sector before reading or
+    /// writing any of its blocks.
+    ///
+    /// `block` is any block address within the sector to authenticate,
+    /// `key` is the 6-byte sector key, and `uid` is the 4-byte UID returned
+    /// by [`select`](crate::Mfrc522::select) (only the first 4 bytes are
+    /// used, as required by the MFAuthent command).
+    pub fn authenticate(&mut self, which: Key, block: u8, key: &[u8; 6], uid: &[u8]) -> Result<(), StatusCode> {
+        let mut payload = Vec::with_capacity(12);
+        payload.push(which.command_byte());
+        payload.push(block);
+        payload.extend_from_slice(key);
+        payload.extend_from_slice(&uid[..4]);
+
+        self.command(Command::Idle)?;
+        self.write_reg(Register::ComIrqReg, 0x7F)?;
+        self.flush_fifo()?;
+        for byte in payload {
+            self.write_reg(Register::FIFODataReg, byte)?;
+        }
+        self.command(Command::MFAuthent)?;
+
+        // MFAuthent raises IdleIRq when the handshake completes; it never
+        // produces FIFO data, so there is nothing to read back.
+        let mut irq = 0;
+        for _ in 0..2000 {
+            irq = self.read_reg(Register::ComIrqReg)?;
+            if irq & 0x10 != 0 {
+                break;
+            }
+        }
+        if irq & 0x10 == 0 {
+            return Err(StatusCode::Timeout);
+        }
+
+        // Status2Reg bit 3 (MFCrypto1On) only becomes set once CRYPTO1 has
+        // actually been switched on, confirming the handshake succeeded.
+        if self.read_reg(Register::Status2Reg)? & 0x08 == 0 {
+            return Err(StatusCode::Incomplete);
+        }
+        Ok(())
+    }
+
+    /// Turns CRYPTO1 back off, so a new PICC can be selected and
+    /// authenticated against.
+    pub fn stop_crypto1(&mut self) -> Result<(), StatusCode> {
+        self.clear_bit_mask(Register::Status2Reg, 0x08)
+    }
+
+    /// Reads one 16-byte block from an authenticated MIFARE Classic sector.
+    pub fn mifare_read(&mut self, block: u8) -> Result<[u8; 16], StatusCode> {
+        let response = self.transceive_with_crc(&[0x30, block])?;
+        // The chip appends the 2 CRC_A bytes it received to the FIFO too.
+        if response.len() != 18 {
+            return Err(StatusCode::Incomplete);
+        }
+        let mut data = [0u8; 16];
+        data.copy_from_slice(&response[..16]);
+        Ok(data)
+    }
+
+    /// Writes one 16-byte block on an authenticated MIFARE Classic sector,
+    /// using the two-stage write handshake (command, then data), each
+    /// stage acknowledged by the PICC with a 4-bit ACK.
+    pub fn mifare_write(&mut self, block: u8, data: &[u8; 16]) -> Result<(), StatusCode> {
+        self.expect_ack(&[0xA0, block])?;
+        self.expect_ack(data)?;
+        Ok(())
+    }
+
+    /// Reads the value block at `block` and decodes the signed 32-bit value
+    /// and its backup address, validating the triple-redundant encoding the
+    /// PICC stores them in.
+    pub fn mifare_read_value(&mut self, block: u8) -> Result<(i32, u8), StatusCode> {
+        let data = self.mifare_read(block)?;
+        decode_value_block(&data)
+    }
+
+    /// Formats `block` as a value block holding `value`, with `addr` as its
+    /// backup address byte.
+    pub fn mifare_write_value(&mut self, block: u8, value: i32, addr: u8) -> Result<(), StatusCode> {
+        self.mifare_write(block, &encode_value_block(value, addr))
+    }
+
+    /// Adds `delta` to the value block at `block`, buffering the result in
+    /// the PICC's internal data register. The caller must still call
+    /// [`mifare_transfer`](Self::mifare_transfer) to commit it to `block`.
+    pub fn mifare_increment(&mut self, block: u8, delta: u32) -> Result<(), StatusCode> {
+        self.expect_ack(&[0xC1, block])?;
+        self.send_unacknowledged(&delta.to_le_bytes())
+    }
+
+    /// Subtracts `delta` from the value block at `block`, buffering the
+    /// result in the PICC's internal data register. The caller must still
+    /// call [`mifare_transfer`](Self::mifare_transfer) to commit it to
+    /// `block`.
+    pub fn mifare_decrement(&mut self, block: u8, delta: u32) -> Result<(), StatusCode> {
+        self.expect_ack(&[0xC0, block])?;
+        self.send_unacknowledged(&delta.to_le_bytes())
+    }
+
+    /// Loads the value block at `block` into the PICC's internal data
+    /// register. The caller must still call
+    /// [`mifare_transfer`](Self::mifare_transfer) to commit it elsewhere.
+    pub fn mifare_restore(&mut self, block: u8) -> Result<(), StatusCode> {
+        self.expect_ack(&[0xC2, block])?;
+        self.send_unacknowledged(&0u32.to_le_bytes())
+    }
+
+    /// Commits the PICC's internal data register, as loaded by
+    /// [`mifare_increment`](Self::mifare_increment),
+    /// [`mifare_decrement`](Self::mifare_decrement) or
+    /// [`mifare_restore`](Self::mifare_restore), to `block`.
+    pub fn mifare_transfer(&mut self, block: u8) -> Result<(), StatusCode> {
+        self.expect_ack(&[0xB0, block])
+    }
+
+    /// Sends `frame` with the TxCRCEn bit set and expects the PICC to reply
+    /// with a 4-bit ACK (0x0A), as MIFARE write/value commands do at each
+    /// stage of their handshake.
+    fn expect_ack(&mut self, frame: &[u8]) -> Result<(), StatusCode> {
+        self.set_bit_mask(Register::TxModeReg, 0x80)?;
+        let result = self.transceive(frame);
+        self.clear_bit_mask(Register::TxModeReg, 0x80)?;
+        let (rx, valid_bits) = result?;
+        if rx.len() != 1 || valid_bits != 4 || rx[0] & 0x0F != 0x0A {
+            return Err(StatusCode::MifareNak);
+        }
+        Ok(())
+    }
+
+    /// Sends `frame` with the TxCRCEn bit set, for the second stage of
+    /// Increment/Decrement/Restore, which — unlike Write — the PICC does
+    /// not acknowledge: it silently buffers the value until a later
+    /// [`mifare_transfer`](Self::mifare_transfer), and only answers (with a
+    /// NAK) when it rejects the frame. A timeout here is therefore the
+    /// expected, successful outcome; any answer is a [`StatusCode::MifareNak`].
+    fn send_unacknowledged(&mut self, frame: &[u8]) -> Result<(), StatusCode> {
+        self.set_bit_mask(Register::TxModeReg, 0x80)?;
+        let result = self.transceive(frame);
+        self.clear_bit_mask(Register::TxModeReg, 0x80)?;
+        match result {
+            Err(StatusCode::Timeout) => Ok(()),
+            Ok(_) => Err(StatusCode::MifareNak),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Encodes `value` and `addr` into the redundant 16-byte MIFARE value-block
+/// format: value, ~value, value, then the backup address byte four times
+/// as `addr, !addr, addr, !addr`.
+pub(crate) fn encode_value_block(value: i32, addr: u8) -> [u8; 16] {
+    let mut buf = [0u8; 16];
+    buf[0..4].copy_from_slice(&value.to_le_bytes());
+    buf[4..8].copy_from_slice(&(!value).to_le_bytes());
+    buf[8..12].copy_from_slice(&value.to_le_bytes());
+    buf[12] = addr;
+    buf[13] = !addr;
+    buf[14] = addr;
+    buf[15] = !addr;
+    buf
+}
+
+/// Decodes and validates a 16-byte MIFARE value block, returning the signed
+/// value and backup address. Fails with [`StatusCode::Incomplete`] if the
+/// redundant copies don't match, which indicates a torn write or a block
+/// that was never formatted as a value block.
+pub(crate) fn decode_value_block(buf: &[u8; 16]) -> Result<(i32, u8), StatusCode> {
+    let value = i32::from_le_bytes(buf[0..4].try_into().unwrap());
+    let inv_value = i32::from_le_bytes(buf[4..8].try_into().unwrap());
+    let value_copy = i32::from_le_bytes(buf[8..12].try_into().unwrap());
+    if inv_value != !value || value_copy != value {
+        return Err(StatusCode::Incomplete);
+    }
+
+    let (addr, inv_addr, addr_copy, addr_copy2) = (buf[12], buf[13], buf[14], buf[15]);
+    if inv_addr != !addr || addr_copy != addr || addr_copy2 != inv_addr {
+        return Err(StatusCode::Incomplete);
+    }
+
+    Ok((value, addr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_a_positive_value() {
+        let buf = encode_value_block(1_000, 4);
+        assert_eq!(decode_value_block(&buf).unwrap(), (1_000, 4));
+    }
+
+    #[test]
+    fn round_trips_a_negative_value() {
+        let buf = encode_value_block(-42, 0xFF);
+        assert_eq!(decode_value_block(&buf).unwrap(), (-42, 0xFF));
+    }
+
+    #[test]
+    fn rejects_a_corrupted_value_copy() {
+        let mut buf = encode_value_block(10, 1);
+        buf[8] ^= 0xFF; // corrupt the second value copy
+        assert!(matches!(decode_value_block(&buf), Err(StatusCode::Incomplete)));
+    }
+
+    #[test]
+    fn rejects_a_corrupted_address_copy() {
+        let mut buf = encode_value_block(10, 1);
+        buf[14] ^= 0xFF; // corrupt the second address copy
+        assert!(matches!(decode_value_block(&buf), Err(StatusCode::Incomplete)));
+    }
+}
@@ -0,0 +1,208 @@
+//! PICC (proximity card) level commands and the anti-collision algorithm
+//!
+//! These are the commands a PCD sends over the RF field to the card itself,
+//! as opposed to the [`register::Command`](crate::register::Command) values
+//! which only control the MFRC522 chip.
+
+use crate::error::StatusCode;
+use crate::register::Register;
+use crate::uid::Uid;
+use crate::Mfrc522;
+
+/// Packs the `RxAlign` (bits 6:4) and `TxLastBits` (bits 2:0) fields that
+/// make up `BitFramingReg`.
+pub(crate) fn bit_framing(rx_align: u8, tx_last_bits: u8) -> u8 {
+    (rx_align << 4) | tx_last_bits
+}
+
+/// Computes the NVB (Number of Valid Bits) byte and the number of UID
+/// buffer bytes that must be included in an anti-collision frame, given how
+/// many UID bits are already known for the current cascade level.
+///
+/// NVB's upper nibble is the count of *complete* known bytes (plus the 2
+/// header bytes of SEL + NVB itself), and its lower nibble is the count of
+/// known bits within the next, partial byte. The frame must include that
+/// partial byte too, or `TxLastBits` ends up chopping NVB itself down
+/// instead of a real UID byte.
+fn anti_collision_frame(known_bits: u8) -> (u8, usize) {
+    let known_bytes = (known_bits / 8) as usize;
+    let partial_bits = known_bits % 8;
+    let nvb = ((2 + known_bytes as u8) << 4) | partial_bits;
+    let frame_bytes = known_bytes + (partial_bits != 0) as usize;
+    (nvb, frame_bytes)
+}
+
+/// REQuest command, Type A. Invites PICCs in IDLE state to go to READY.
+pub(crate) const PICC_REQA: u8 = 0x26;
+/// Wake UP command, Type A. Invites PICCs in IDLE or HALT state to go to READY.
+const PICC_WUPA: u8 = 0x52;
+/// Cascade Tag, used during anti-collision to signal that more UID bytes follow.
+const PICC_CT: u8 = 0x88;
+
+/// SEL byte for each of the three possible cascade levels.
+const CASCADE_SEL: [u8; 3] = [0x93, 0x95, 0x97];
+
+impl Mfrc522 {
+    /// Puts PICCs in IDLE state within range into READY state and returns
+    /// their ATQA (Answer To reQuest, type A).
+    pub fn request_a(&mut self) -> Result<[u8; 2], StatusCode> {
+        self.reqa_or_wupa(PICC_REQA)
+    }
+
+    /// Wakes PICCs in IDLE or HALT state within range and returns their ATQA.
+    pub fn wakeup_a(&mut self) -> Result<[u8; 2], StatusCode> {
+        self.reqa_or_wupa(PICC_WUPA)
+    }
+
+    fn reqa_or_wupa(&mut self, cmd: u8) -> Result<[u8; 2], StatusCode> {
+        // REQA/WUPA are 7-bit frames, so only the 7 least significant bits of
+        // the single command byte we send are valid.
+        self.write_reg(Register::BitFramingReg, bit_framing(0, 7))?;
+        let (rx, _) = self.transceive(&[cmd])?;
+        if rx.len() != 2 {
+            return Err(StatusCode::Incomplete);
+        }
+        Ok([rx[0], rx[1]])
+    }
+
+    /// Runs the ISO/IEC 14443-3 anti-collision and selection loop to resolve
+    /// the UID of a single PICC within range and select it.
+    ///
+    /// PICCs must already have been woken up with [`request_a`](Self::request_a)
+    /// or [`wakeup_a`](Self::wakeup_a). Returns the card's [`Uid`] and SAK.
+    pub fn select(&mut self) -> Result<Uid, StatusCode> {
+        let mut uid_bytes = Vec::with_capacity(10);
+        let mut sak = 0u8;
+
+        for sel in CASCADE_SEL {
+            let (level_uid, level_sak) = self.select_cascade_level(sel)?;
+            sak = level_sak;
+
+            if level_uid[0] == PICC_CT {
+                // Cascade tag: the real UID bytes continue at the next level,
+                // byte 0 here is only a marker and is discarded.
+                uid_bytes.extend_from_slice(&level_uid[1..4]);
+            } else {
+                uid_bytes.extend_from_slice(&level_uid);
+            }
+
+            // Bit 2 of the SAK: 0 = UID complete, 1 = proceed to next cascade level.
+            if sak & 0x04 == 0 {
+                break;
+            }
+        }
+
+        Ok(Uid::new(uid_bytes, sak))
+    }
+
+    /// Resolves the 4 UID bytes + BCC for a single cascade level, handling
+    /// collisions, then issues the full SELECT for that level.
+    fn select_cascade_level(&mut self, sel: u8) -> Result<([u8; 4], u8), StatusCode> {
+        // Known UID bits collected so far for this level, LSB-first.
+        let mut known_bits: u8 = 0;
+        let mut uid_buf = [0u8; 5]; // 4 UID bytes + BCC
+
+        loop {
+            let (nvb, frame_bytes) = anti_collision_frame(known_bits);
+            let known_bytes = (known_bits / 8) as usize;
+            let tx_last_bits = known_bits % 8;
+
+            let mut frame = Vec::with_capacity(2 + frame_bytes);
+            frame.push(sel);
+            frame.push(nvb);
+            frame.extend_from_slice(&uid_buf[..frame_bytes]);
+
+            let rx_align = tx_last_bits;
+            self.write_reg(Register::BitFramingReg, bit_framing(rx_align, tx_last_bits))?;
+
+            match self.transceive(&frame) {
+                Ok((rx, _valid_bits)) => {
+                    // A full response arrived with no collision: fold it into
+                    // the buffer and move on to the final SELECT below.
+                    for (i, byte) in rx.iter().enumerate() {
+                        if known_bytes + i < uid_buf.len() {
+                            uid_buf[known_bytes + i] = *byte;
+                        }
+                    }
+                    break;
+                }
+                Err(StatusCode::Collision(coll_bit)) => {
+                    // The bits received before the collision are valid and
+                    // still sit in the FIFO: keep them as known bits.
+                    let fifo_level = self.read_reg(Register::FIFOLevelReg)? as usize;
+                    for i in 0..fifo_level {
+                        let byte = self.read_reg(Register::FIFODataReg)?;
+                        if known_bytes + i < uid_buf.len() {
+                            uid_buf[known_bytes + i] = byte;
+                        }
+                    }
+                    // `CollReg` reports the absolute position (0-31) of the
+                    // first colliding bit within the UID. Append that bit as
+                    // a `1` (our guess) and ask again with one more bit known.
+                    let byte_idx = (coll_bit / 8) as usize;
+                    let bit_idx = coll_bit % 8;
+                    uid_buf[byte_idx] |= 1 << bit_idx;
+                    known_bits = coll_bit + 1;
+                    continue;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        // Finish the level with a full, non-anti-collision SELECT and read
+        // back the SAK.
+        let mut select_frame = Vec::with_capacity(7);
+        select_frame.push(sel);
+        select_frame.push(0x70); // NVB: all 40 bits (32 UID/CT + 8 BCC) valid.
+        select_frame.extend_from_slice(&uid_buf);
+        self.write_reg(Register::BitFramingReg, 0x00)?;
+        let select_response = self.transceive_with_crc(&select_frame)?;
+        let sak = *select_response.first().ok_or(StatusCode::Incomplete)?;
+
+        let mut level_uid = [0u8; 4];
+        level_uid.copy_from_slice(&uid_buf[..4]);
+        Ok((level_uid, sak))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bit_framing_packs_rx_align_and_tx_last_bits() {
+        assert_eq!(bit_framing(0, 0), 0x00);
+        assert_eq!(bit_framing(0, 7), 0x07);
+        assert_eq!(bit_framing(3, 5), 0x35);
+    }
+
+    #[test]
+    fn anti_collision_frame_with_no_bits_known() {
+        assert_eq!(anti_collision_frame(0), (0x20, 0));
+    }
+
+    #[test]
+    fn anti_collision_frame_with_partial_byte_known() {
+        // 3 known bits: no complete bytes yet, but the partial byte holding
+        // them must still be sent.
+        assert_eq!(anti_collision_frame(3), (0x23, 1));
+    }
+
+    #[test]
+    fn anti_collision_frame_on_a_byte_boundary() {
+        // 8 known bits is one complete byte and no partial byte.
+        assert_eq!(anti_collision_frame(8), (0x30, 1));
+    }
+
+    #[test]
+    fn anti_collision_frame_with_a_full_byte_plus_partial() {
+        // 11 known bits: 1 complete byte plus 3 bits into the next one.
+        assert_eq!(anti_collision_frame(11), (0x33, 2));
+    }
+
+    #[test]
+    fn anti_collision_frame_with_almost_all_bits_known() {
+        // 31 known bits: 3 complete bytes plus 7 bits into the 4th.
+        assert_eq!(anti_collision_frame(31), (0x57, 4));
+    }
+}
@@ -0,0 +1,30 @@
+//! Soft power-down and wake-up
+
+use crate::error::StatusCode;
+use crate::register::Register;
+use crate::Mfrc522;
+
+/// Maximum number of polling iterations to wait for the chip to report
+/// that it has woken up.
+const WAKE_UP_TIMEOUT_ITERS: u32 = 2000;
+
+impl Mfrc522 {
+    /// Puts the analog front end and crystal oscillator into soft
+    /// power-down, dropping idle current to under 80 uA. No commands can
+    /// be issued until [`power_up`](Self::power_up) is called.
+    pub fn power_down(&mut self) -> Result<(), StatusCode> {
+        self.set_bit_mask(Register::CommandReg, 0x10)
+    }
+
+    /// Wakes the chip back up from soft power-down and blocks until it
+    /// confirms the wake-up procedure has completed.
+    pub fn power_up(&mut self) -> Result<(), StatusCode> {
+        self.clear_bit_mask(Register::CommandReg, 0x10)?;
+        for _ in 0..WAKE_UP_TIMEOUT_ITERS {
+            if self.read_reg(Register::CommandReg)? & 0x10 == 0 {
+                return Ok(());
+            }
+        }
+        Err(StatusCode::Timeout)
+    }
+}
@@ -114,7 +114,7 @@ impl From<Register> for u8 {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 /// The receiver's signal voltage gain factor
 ///
@@ -143,6 +143,48 @@ impl From<RxGain> for u8 {
     }
 }
 
+impl RxGain {
+    /// Recovers the gain level from the raw `RFCfgReg` bits 6:4, mapping
+    /// the `0x20`/`0x30` aliases back onto `DB18`/`DB23` as documented on
+    /// those variants.
+    pub(crate) fn from_bits(bits: u8) -> Self {
+        match bits & 0x70 {
+            0x10 | 0x30 => RxGain::DB23,
+            0x40 => RxGain::DB33,
+            0x50 => RxGain::DB38,
+            0x60 => RxGain::DB43,
+            0x70 => RxGain::DB48,
+            _ => RxGain::DB18,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_bits_recovers_each_canonical_value() {
+        assert_eq!(RxGain::from_bits(0x00), RxGain::DB18);
+        assert_eq!(RxGain::from_bits(0x10), RxGain::DB23);
+        assert_eq!(RxGain::from_bits(0x40), RxGain::DB33);
+        assert_eq!(RxGain::from_bits(0x50), RxGain::DB38);
+        assert_eq!(RxGain::from_bits(0x60), RxGain::DB43);
+        assert_eq!(RxGain::from_bits(0x70), RxGain::DB48);
+    }
+
+    #[test]
+    fn from_bits_maps_documented_aliases() {
+        assert_eq!(RxGain::from_bits(0x20), RxGain::DB18);
+        assert_eq!(RxGain::from_bits(0x30), RxGain::DB23);
+    }
+
+    #[test]
+    fn from_bits_ignores_unrelated_bits() {
+        assert_eq!(RxGain::from_bits(0x4F), RxGain::DB33);
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(u8)]
 /// List of different commands for the MFRC522
@@ -0,0 +1,33 @@
+//! PICC identifiers returned by the anti-collision loop
+
+/// The UID of a PICC, resolved through one, two or three cascade levels.
+///
+/// Single cascade level PICCs have a 4-byte UID, two levels produce a
+/// 7-byte UID, and three levels produce a 10-byte UID. `bytes` always holds
+/// only the significant UID bytes (the `0x88` cascade tag used internally to
+/// chain levels together is not included).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Uid {
+    bytes: Vec<u8>,
+    /// The SAK (Select Acknowledge) byte returned by the PICC for the final
+    /// cascade level, which identifies the card type.
+    pub sak: u8,
+}
+
+impl Uid {
+    pub(crate) fn new(bytes: Vec<u8>, sak: u8) -> Self {
+        Uid { bytes, sak }
+    }
+
+    /// The UID bytes, 4, 7 or 10 bytes long depending on how many cascade
+    /// levels were needed to resolve it.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// `true` if bit 2 of the SAK is clear, meaning this PICC is not a
+    /// further cascade tag and the UID above is complete.
+    pub fn is_complete(&self) -> bool {
+        self.sak & 0x04 == 0
+    }
+}